@@ -1,16 +1,66 @@
 use std::process::Command;
 
-fn main() {
-    // Short git commit SHA
-    let sha = Command::new("git")
-        .args(["rev-parse", "--short", "HEAD"])
+fn git_stdout(args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .args(args)
         .output()
         .ok()
+        .filter(|o| o.status.success())
         .and_then(|o| String::from_utf8(o.stdout).ok())
         .map(|s| s.trim().to_string())
-        .unwrap_or_else(|| "unknown".into());
+}
+
+/// Tell cargo to re-run this build script whenever `HEAD` moves (commit, checkout, or
+/// branch switch) or the index changes (staging/unstaging), so `GIT_SHA` tracks `HEAD`
+/// across incremental builds. The dirty flag and `BUILD_DATE` are recomputed only on
+/// those same triggers (or a change to `build.rs`); editing a worktree file without
+/// touching the index does not re-run the script.
+fn watch_git_dir() {
+    let Some(git_dir) = git_stdout(&["rev-parse", "--git-dir"]) else {
+        return;
+    };
+    let git_dir = std::path::Path::new(&git_dir);
+
+    let head = git_dir.join("HEAD");
+    println!("cargo:rerun-if-changed={}", head.display());
+
+    // If HEAD is a symbolic ref (the normal case), also watch the ref file it points
+    // at, since committing on a branch only touches that file, not HEAD itself.
+    if let Ok(contents) = std::fs::read_to_string(&head) {
+        if let Some(ref_path) = contents.trim().strip_prefix("ref: ") {
+            println!("cargo:rerun-if-changed={}", git_dir.join(ref_path).display());
+        }
+    }
+
+    println!("cargo:rerun-if-changed={}", git_dir.join("index").display());
+}
+
+fn main() {
+    watch_git_dir();
+
+    // Short git commit SHA
+    let sha = git_stdout(&["rev-parse", "--short", "HEAD"]);
+    println!(
+        "cargo:rustc-env=GIT_SHA={}",
+        sha.as_deref().unwrap_or("unknown")
+    );
 
-    println!("cargo:rustc-env=GIT_SHA={}", sha);
+    // Current branch, if any. `symbolic-ref -q` fails cleanly (no stdout) when HEAD is
+    // detached; `rev-parse --abbrev-ref HEAD` would instead succeed and print the
+    // literal string "HEAD", which is not a branch name and would misreport CI builds
+    // (which routinely check out a detached SHA/tag) as being "on HEAD".
+    let branch = git_stdout(&["symbolic-ref", "-q", "--short", "HEAD"]);
+
+    // Dirty flag: any porcelain output means the worktree has uncommitted changes.
+    let dirty = Command::new("git")
+        .args(["status", "--porcelain"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| !o.stdout.is_empty());
+
+    // Commit timestamp, ISO-8601 with offset.
+    let committed_at = git_stdout(&["log", "-1", "--format=%cI"]);
 
     // Build date (UTC)
     let build_date = Command::new("date")
@@ -37,4 +87,95 @@ fn main() {
     // Build profile (release/debug)
     let profile = std::env::var("PROFILE").unwrap_or_else(|_| "unknown".into());
     println!("cargo:rustc-env=BUILD_PROFILE={}", profile);
+
+    // Cargo-provided target/host triples and package metadata, passed straight through
+    // so `BuildInfo` doesn't have to re-derive them at runtime.
+    let target = std::env::var("TARGET").unwrap_or_else(|_| "unknown".into());
+    let host = std::env::var("HOST").unwrap_or_else(|_| "unknown".into());
+    let pkg_version = std::env::var("CARGO_PKG_VERSION").unwrap_or_else(|_| "unknown".into());
+    let pkg_name = std::env::var("CARGO_PKG_NAME").unwrap_or_else(|_| "unknown".into());
+    // CARGO_ENCODED_RUSTFLAGS is unit-separator (\x1f) delimited; split it into
+    // individual flags so BuildInfo holds readable strings instead of a single string
+    // glued together with control characters.
+    let rustflags: Vec<String> = std::env::var("CARGO_ENCODED_RUSTFLAGS")
+        .unwrap_or_default()
+        .split('\u{1f}')
+        .filter(|f| !f.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    // Codegen a `build_info.rs` into OUT_DIR that constructs a `BuildInfo` literal at
+    // compile time, so the rest of the crate never parses env strings by hand.
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR not set by cargo");
+    let dest = std::path::Path::new(&out_dir).join("build_info.rs");
+
+    fn opt_str_literal(v: &Option<String>) -> String {
+        match v {
+            Some(s) => format!("Some({s:?}.to_string())"),
+            None => "None".to_string(),
+        }
+    }
+
+    fn string_vec_literal(items: &[String]) -> String {
+        let entries = items
+            .iter()
+            .map(|s| format!("{s:?}.to_string()"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("vec![{entries}]")
+    }
+
+    let git_info = if let Some(rev) = &sha {
+        format!(
+            "Some(GitInfo {{ rev: {rev:?}.to_string(), branch: {branch}, dirty: {dirty:?}, committed_at: {committed_at} }})",
+            branch = opt_str_literal(&branch),
+            committed_at = opt_str_literal(&committed_at),
+        )
+    } else {
+        "None".to_string()
+    };
+
+    let generated = format!(
+        "fn build_info() -> BuildInfo {{\n\
+         \x20   BuildInfo {{\n\
+         \x20       git: {git_info},\n\
+         \x20       pkg_version: {pkg_version:?}.to_string(),\n\
+         \x20       pkg_name: {pkg_name:?}.to_string(),\n\
+         \x20       target: {target:?}.to_string(),\n\
+         \x20       host: {host:?}.to_string(),\n\
+         \x20       rustc_version: env!(\"RUSTC_VERSION\").to_string(),\n\
+         \x20       profile: env!(\"BUILD_PROFILE\").to_string(),\n\
+         \x20       rustflags: {rustflags},\n\
+         \x20       built_at: env!(\"BUILD_DATE\").to_string(),\n\
+         \x20   }}\n\
+         }}\n",
+        rustflags = string_vec_literal(&rustflags),
+    );
+
+    std::fs::write(&dest, generated).expect("failed to write build_info.rs to OUT_DIR");
+
+    // Also drop a plain-text sidecar next to the build artifact (OUT_DIR is
+    // `target/<profile>/build/<pkg>-<hash>/out`, three levels below `target/<profile>`),
+    // so provenance can be verified on an already-deployed binary whose source tree is
+    // gone, without recompiling or digging through embedded env strings.
+    if let Some(artifact_dir) = std::path::Path::new(&out_dir)
+        .ancestors()
+        .nth(3)
+    {
+        let sidecar = format!(
+            "timestamp={build_date}\n\
+             git_commit={git_commit}\n\
+             git_branch={git_branch}\n\
+             dirty={dirty}\n\
+             rustc_version={rustc_version}\n\
+             target={target}\n\
+             profile={profile}\n",
+            git_commit = sha.as_deref().unwrap_or("unknown"),
+            git_branch = branch.as_deref().unwrap_or("unknown"),
+            dirty = dirty.map(|d| d.to_string()).unwrap_or_else(|| "unknown".into()),
+        );
+        let _ = std::fs::write(artifact_dir.join("build-data.txt"), sidecar);
+    }
+
+    println!("cargo:rerun-if-changed=build.rs");
 }
@@ -0,0 +1,131 @@
+//! Loader for the `build-data.txt` sidecar that `build.rs` drops next to the build
+//! artifact (key=value lines). Unlike [`crate::build_info`], this is read from disk at
+//! runtime, so it can verify provenance of a binary whose source tree no longer exists.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+/// Parsed `build-data.txt` contents, keyed by field name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildData(BTreeMap<String, String>);
+
+impl BuildData {
+    /// Parse `key=value` lines, skipping blanks and anything without an `=`.
+    pub fn parse(contents: &str) -> BuildData {
+        let mut fields = BTreeMap::new();
+        for line in contents.lines() {
+            let line = line.trim();
+            if let Some((key, value)) = line.split_once('=') {
+                fields.insert(key.trim().to_string(), value.trim().to_string());
+            }
+        }
+        BuildData(fields)
+    }
+
+    /// Load and parse a sidecar file from disk.
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<BuildData> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(BuildData::parse(&contents))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    /// Fields that are absent on one side, or have a different value, in `self` vs `other`.
+    ///
+    /// Returns one [`FieldDiff`] per key where the two sidecars disagree, including
+    /// keys only one side has.
+    pub fn diff(&self, other: &BuildData) -> Vec<FieldDiff> {
+        let mut keys: Vec<&String> = self.0.keys().chain(other.0.keys()).collect();
+        keys.sort();
+        keys.dedup();
+
+        keys.into_iter()
+            .filter_map(|key| {
+                let left = self.0.get(key).map(String::as_str);
+                let right = other.0.get(key).map(String::as_str);
+                if left == right {
+                    None
+                } else {
+                    Some(FieldDiff {
+                        key: key.clone(),
+                        left: left.map(str::to_string),
+                        right: right.map(str::to_string),
+                    })
+                }
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for BuildData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (key, value) in &self.0 {
+            writeln!(f, "{key}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A single field where two [`BuildData`] sidecars disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    pub key: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+impl fmt::Display for FieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} != {}",
+            self.key,
+            self.left.as_deref().unwrap_or("<missing>"),
+            self.right.as_deref().unwrap_or("<missing>")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_key_value_lines_and_skips_junk() {
+        let data = BuildData::parse("timestamp=2024-01-01\n\ngit_commit = abc123 \nnot a field\n");
+        assert_eq!(data.get("timestamp"), Some("2024-01-01"));
+        assert_eq!(data.get("git_commit"), Some("abc123"));
+        assert_eq!(data.get("missing"), None);
+    }
+
+    #[test]
+    fn diff_is_empty_for_identical_data() {
+        let a = BuildData::parse("git_commit=abc123\ndirty=false\n");
+        let b = BuildData::parse("git_commit=abc123\ndirty=false\n");
+        assert!(a.diff(&b).is_empty());
+    }
+
+    #[test]
+    fn diff_reports_changed_and_missing_fields() {
+        let a = BuildData::parse("git_commit=abc123\ndirty=false\n");
+        let b = BuildData::parse("git_commit=def456\nbranch=main\n");
+
+        let diff = a.diff(&b);
+        assert_eq!(diff.len(), 3);
+
+        let commit_diff = diff.iter().find(|d| d.key == "git_commit").unwrap();
+        assert_eq!(commit_diff.left.as_deref(), Some("abc123"));
+        assert_eq!(commit_diff.right.as_deref(), Some("def456"));
+
+        let dirty_diff = diff.iter().find(|d| d.key == "dirty").unwrap();
+        assert_eq!(dirty_diff.left.as_deref(), Some("false"));
+        assert_eq!(dirty_diff.right, None);
+
+        let branch_diff = diff.iter().find(|d| d.key == "branch").unwrap();
+        assert_eq!(branch_diff.left, None);
+        assert_eq!(branch_diff.right.as_deref(), Some("main"));
+    }
+}
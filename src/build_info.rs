@@ -0,0 +1,65 @@
+//! Structured build provenance, captured by `build.rs` and baked into the binary.
+//!
+//! `build.rs` codegens a `build_info()` constructor function into `OUT_DIR`, which we
+//! `include!` here rather than parsing loose env strings at the call site.
+
+use serde::Serialize;
+
+/// Git state of the worktree that produced this binary, or `None` if `git` was
+/// unavailable at build time.
+#[derive(Debug, Clone, Serialize)]
+pub struct GitInfo {
+    pub rev: String,
+    pub branch: Option<String>,
+    pub dirty: Option<bool>,
+    pub committed_at: Option<String>,
+}
+
+/// Everything needed to identify exactly which source state and toolchain produced
+/// this binary, for attestation and reproducibility checks.
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildInfo {
+    pub git: Option<GitInfo>,
+    pub pkg_version: String,
+    pub pkg_name: String,
+    pub target: String,
+    pub host: String,
+    pub rustc_version: String,
+    pub profile: String,
+    /// Decoded `RUSTFLAGS` passed to rustc, one flag per entry (cargo encodes these
+    /// `\x1f`-delimited in `CARGO_ENCODED_RUSTFLAGS`; `build.rs` splits that out so
+    /// this is directly readable, not escaped control characters).
+    pub rustflags: Vec<String>,
+    pub built_at: String,
+}
+
+include!(concat!(env!("OUT_DIR"), "/build_info.rs"));
+
+impl BuildInfo {
+    /// The `BuildInfo` for the binary currently running.
+    pub fn current() -> BuildInfo {
+        build_info()
+    }
+
+    /// One-line human summary, e.g. `trustee 0.1.0 (a1b2c3d on main, dirty)`.
+    pub fn summary(&self) -> String {
+        match &self.git {
+            Some(git) => {
+                let dirty = match git.dirty {
+                    Some(true) => ", dirty",
+                    _ => "",
+                };
+                let branch = git
+                    .branch
+                    .as_deref()
+                    .map(|b| format!(" on {b}"))
+                    .unwrap_or_default();
+                format!(
+                    "{} {} ({}{branch}{dirty})",
+                    self.pkg_name, self.pkg_version, git.rev
+                )
+            }
+            None => format!("{} {} (no git info)", self.pkg_name, self.pkg_version),
+        }
+    }
+}
@@ -1,6 +1,146 @@
 //! Trustee - A general-purpose agent that can morph into different specialized agents
+//!
+//! KNOWN GAP: `version`, `build-data`, and `release` below were all originally scoped
+//! to live as `abk::cli` command modules (`release` additionally as a mode selected
+//! through `config/trustee.toml`, not a CLI word). `abk::cli` isn't vendored in this
+//! checkout, so its dispatch contract can't be inspected from here, and these are
+//! instead intercepted directly in `main` ahead of
+//! `abk::cli::run_configured_cli_from_config`. That means these three words
+//! permanently shadow any `abk::cli`-dispatched agent mode of the same name, with no
+//! fallback if abk already defines one. Treat this as a blocking integration question
+//! to confirm against abk::cli's actual contract, not a settled design — it should move
+//! into `abk::cli` once that crate is available in-tree.
+
+mod build_data;
+mod build_info;
+mod release;
+
+use build_data::BuildData;
+use build_info::BuildInfo;
+use release::{ReleaseConfig, extract_changelog_section, read_package_meta, tag_and_push};
+
+/// Path to the `build-data.txt` sidecar dropped by `build.rs` next to this binary.
+fn sidecar_path() -> std::io::Result<std::path::PathBuf> {
+    Ok(std::env::current_exe()?
+        .parent()
+        .expect("binary path has a parent directory")
+        .join("build-data.txt"))
+}
+
+/// Top-level keys `main` reads out of `config/trustee.toml` before handing off to
+/// `abk::cli`. Only `mode` and the `[release]` table are consulted here; everything
+/// else in the file is abk's.
+#[derive(Debug, Default, serde::Deserialize)]
+struct ModeConfig {
+    /// Selects a specialized agent mode, e.g. `"release"`. Unset means "let abk::cli's
+    /// own config-driven selection decide", once that's wired up.
+    #[serde(default)]
+    mode: Option<String>,
+    #[serde(default)]
+    release: Option<ReleaseConfig>,
+}
+
+fn load_mode_config(path: &str) -> std::io::Result<ModeConfig> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Ok(toml::from_str(&contents).unwrap_or_default()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(ModeConfig::default()),
+        Err(e) => Err(e),
+    }
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    abk::cli::run_configured_cli_from_config("config/trustee.toml").await
-}
\ No newline at end of file
+    let config_path = "config/trustee.toml";
+    let mode_config = load_mode_config(config_path)?;
+
+    if mode_config.mode.as_deref() == Some("release") {
+        let release_config = mode_config
+            .release
+            .ok_or("mode = \"release\" set but config has no [release] table")?;
+        return run_release(&release_config).await;
+    }
+
+    let mut args = std::env::args().skip(1);
+    match args.next().as_deref() {
+        Some("version") => {
+            let info = BuildInfo::current();
+            if args.any(|a| a == "--json") {
+                println!("{}", serde_json::to_string_pretty(&info)?);
+            } else {
+                println!("{}", info.summary());
+            }
+            return Ok(());
+        }
+        Some("build-data") => {
+            match args.next().as_deref() {
+                Some("diff") => {
+                    let (left_path, right_path) = (
+                        args.next().ok_or("usage: build-data diff <a> <b>")?,
+                        args.next().ok_or("usage: build-data diff <a> <b>")?,
+                    );
+                    let left = BuildData::load(&left_path)?;
+                    let right = BuildData::load(&right_path)?;
+                    let diff = left.diff(&right);
+                    if diff.is_empty() {
+                        println!("identical");
+                    } else {
+                        for field in diff {
+                            println!("{field}");
+                        }
+                    }
+                }
+                Some("get") => {
+                    let key = args.next().ok_or("usage: build-data get <key>")?;
+                    let data = BuildData::load(sidecar_path()?)?;
+                    println!(
+                        "{}",
+                        data.get(&key)
+                            .ok_or_else(|| format!("no such field: {key}"))?
+                    );
+                }
+                Some(other) => return Err(format!("unknown build-data subcommand: {other}").into()),
+                None => {
+                    let data = BuildData::load(sidecar_path()?)?;
+                    print!("{data}");
+                }
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    warn_if_dirty();
+    abk::cli::run_configured_cli_from_config(config_path).await
+}
+
+/// Read the project's version/changelog and cut a release: tag `HEAD` and push it.
+/// Selected via `mode = "release"` in `config/trustee.toml`, per the original request,
+/// rather than a CLI word.
+async fn run_release(release_config: &ReleaseConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let pkg = read_package_meta("Cargo.toml")?;
+    let changelog = std::fs::read_to_string("CHANGELOG.md")?;
+    let body = extract_changelog_section(&changelog, &pkg.version)
+        .ok_or_else(|| release::ReleaseError::MissingVersionSection(pkg.version.clone()))?;
+
+    if tag_and_push(&pkg.version, &body, release_config)? {
+        println!("tagged and pushed {} v{}", pkg.name, pkg.version);
+    } else {
+        println!("v{} already tagged, nothing to do", pkg.version);
+    }
+    Ok(())
+}
+
+/// Warn loudly on stderr if this binary was built from an uncommitted worktree, since
+/// trustee morphs into specialized agents and an operator needs to know a running
+/// agent doesn't correspond to any committed source state.
+fn warn_if_dirty() {
+    if let Some(git) = &BuildInfo::current().git {
+        if git.dirty == Some(true) {
+            eprintln!(
+                "warning: this binary was built from a dirty worktree (commit {}); \
+                 it does not correspond to any committed source state",
+                git.rev
+            );
+        }
+    }
+}
@@ -0,0 +1,294 @@
+//! Self-release agent: cuts a git tag straight from `Cargo.toml`'s version and the
+//! matching `CHANGELOG.md` section, then pushes it.
+//!
+//! This gives a one-command "cut a release" flow driven entirely by the repo's own
+//! changelog and version metadata, with no separate release-notes authoring step.
+
+use std::fmt;
+use std::process::Command;
+
+/// Tagger identity used when signing the annotated tag.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct Signature {
+    pub name: String,
+    pub email: String,
+}
+
+/// `[release]` section of the agent's TOML config.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ReleaseConfig {
+    /// Remote to push the tag to, e.g. `"origin"`.
+    pub remote: String,
+    pub signature: Signature,
+    /// Push token, read directly from config. Prefer `token_env` for anything checked
+    /// into version control.
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Name of an environment variable to read the push token from.
+    #[serde(default)]
+    pub token_env: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum ReleaseError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    MissingVersionSection(String),
+    MissingToken,
+    Git(String),
+}
+
+impl fmt::Display for ReleaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReleaseError::Io(e) => write!(f, "io error: {e}"),
+            ReleaseError::Toml(e) => write!(f, "toml error: {e}"),
+            ReleaseError::MissingVersionSection(v) => {
+                write!(f, "CHANGELOG.md has no section for version {v}")
+            }
+            ReleaseError::MissingToken => write!(f, "no push token configured (token/token_env)"),
+            ReleaseError::Git(msg) => write!(f, "git error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for ReleaseError {}
+
+impl From<std::io::Error> for ReleaseError {
+    fn from(e: std::io::Error) -> Self {
+        ReleaseError::Io(e)
+    }
+}
+
+impl From<toml::de::Error> for ReleaseError {
+    fn from(e: toml::de::Error) -> Self {
+        ReleaseError::Toml(e)
+    }
+}
+
+/// `package.name` / `package.version` read out of a `Cargo.toml`.
+pub struct PackageMeta {
+    pub name: String,
+    pub version: String,
+}
+
+pub fn read_package_meta(cargo_toml_path: impl AsRef<std::path::Path>) -> Result<PackageMeta, ReleaseError> {
+    #[derive(serde::Deserialize)]
+    struct Manifest {
+        package: Package,
+    }
+    #[derive(serde::Deserialize)]
+    struct Package {
+        name: String,
+        version: String,
+    }
+
+    let contents = std::fs::read_to_string(cargo_toml_path)?;
+    let manifest: Manifest = toml::from_str(&contents)?;
+    Ok(PackageMeta {
+        name: manifest.package.name,
+        version: manifest.package.version,
+    })
+}
+
+/// Pull the version out of a top-level changelog heading, e.g. `## [1.2.0] - 2024-01-01`
+/// or `## 1.2.0` both yield `Some("1.2.0")`. Returns `None` for lines that aren't a
+/// top-level (`## `) heading at all.
+fn heading_version(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("## ")?.trim_start();
+    if let Some(inner) = rest.strip_prefix('[') {
+        inner.split(']').next()
+    } else {
+        rest.split_whitespace().next()
+    }
+}
+
+/// Extract the body of the `CHANGELOG.md` section for `version`, accepting both
+/// `## 0.1.0` and `## [0.1.0]` headings (with an optional trailing ` - date`),
+/// including nested `### ADDED`/`### FIXED` subsections, up to (but not including) the
+/// next top-level `## ` heading.
+///
+/// Matches the heading's version exactly, not as a prefix — `## 1.2.0-rc1` must not
+/// match a lookup for `"1.2.0"`.
+pub fn extract_changelog_section(changelog: &str, version: &str) -> Option<String> {
+    let mut lines = changelog.lines();
+    let mut body = String::new();
+    let mut in_section = false;
+
+    for line in &mut lines {
+        if in_section {
+            if line.starts_with("## ") {
+                break;
+            }
+            body.push_str(line);
+            body.push('\n');
+        } else if heading_version(line) == Some(version) {
+            in_section = true;
+        }
+    }
+
+    if in_section {
+        Some(body.trim().to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_plain_heading() {
+        let changelog = "# Changelog\n\n## 1.2.0\n\n### ADDED\n- thing\n\n## 1.1.0\n\n- older\n";
+        assert_eq!(
+            extract_changelog_section(changelog, "1.2.0").as_deref(),
+            Some("### ADDED\n- thing")
+        );
+    }
+
+    #[test]
+    fn matches_bracketed_heading_with_date() {
+        let changelog = "## [1.2.0] - 2024-01-01\n\n### FIXED\n- bug\n\n## [1.1.0] - 2023-01-01\n- older\n";
+        assert_eq!(
+            extract_changelog_section(changelog, "1.2.0").as_deref(),
+            Some("### FIXED\n- bug")
+        );
+    }
+
+    #[test]
+    fn does_not_match_prerelease_suffix_as_prefix() {
+        // A naive prefix check would treat "## 1.2.0-rc1" as matching "1.2.0".
+        let changelog =
+            "## 1.2.0-rc1\n\n- preview notes\n\n## 1.2.0\n\n- real release notes\n";
+        assert_eq!(
+            extract_changelog_section(changelog, "1.2.0").as_deref(),
+            Some("- real release notes")
+        );
+    }
+
+    #[test]
+    fn missing_version_returns_none() {
+        let changelog = "## 1.1.0\n\n- older\n";
+        assert_eq!(extract_changelog_section(changelog, "1.2.0"), None);
+    }
+}
+
+fn run_git(args: &[&str]) -> Result<(), ReleaseError> {
+    run_git_with_env(args, &[])
+}
+
+fn run_git_with_env(args: &[&str], env: &[(&str, String)]) -> Result<(), ReleaseError> {
+    let output = Command::new("git")
+        .args(args)
+        .envs(env.iter().map(|(k, v)| (*k, v.as_str())))
+        .output()
+        .map_err(|e| ReleaseError::Git(e.to_string()))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(ReleaseError::Git(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+/// Whether `tag` already exists on `remote`, checked with `git ls-remote` rather than a
+/// local ref lookup — a local tag can exist without ever having been pushed (e.g. a
+/// prior run that failed after tagging), and the local ref is not evidence the release
+/// actually went out.
+fn tag_exists_on_remote(remote: &str, tag: &str) -> Result<bool, ReleaseError> {
+    let output = Command::new("git")
+        .args(["ls-remote", "--exit-code", "--tags", remote, &format!("refs/tags/{tag}")])
+        .output()
+        .map_err(|e| ReleaseError::Git(e.to_string()))?;
+
+    match output.status.code() {
+        Some(0) => Ok(true),
+        Some(2) => Ok(false), // `--exit-code` reserves 2 for "ref not found"
+        _ => Err(ReleaseError::Git(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        )),
+    }
+}
+
+/// Tag `HEAD` as `v{version}` with an annotated tag whose message is `changelog_body`,
+/// signed with `config.signature`, then push it to `config.remote`. No-op (returns
+/// `Ok(false)`) if the tag is already on the remote.
+///
+/// Checking/pushing is treated as one failure-atomic unit: the push token is resolved
+/// *before* any local tag is created, and if the push itself fails, a tag created by
+/// this call is rolled back. Otherwise a missing token or failed push would leave a
+/// local-only tag behind, and the next run would reuse that local tag as-is and push
+/// it, even if `HEAD` or the changelog had moved on since it was created.
+pub fn tag_and_push(
+    version: &str,
+    changelog_body: &str,
+    config: &ReleaseConfig,
+) -> Result<bool, ReleaseError> {
+    let tag = format!("v{version}");
+
+    if tag_exists_on_remote(&config.remote, &tag)? {
+        return Ok(false);
+    }
+
+    let token = config
+        .token
+        .clone()
+        .or_else(|| {
+            config
+                .token_env
+                .as_deref()
+                .and_then(|var| std::env::var(var).ok())
+        })
+        .ok_or(ReleaseError::MissingToken)?;
+
+    let tag_exists_locally = Command::new("git")
+        .args(["rev-parse", "-q", "--verify", &format!("refs/tags/{tag}")])
+        .output()
+        .map_err(|e| ReleaseError::Git(e.to_string()))?
+        .status
+        .success();
+
+    if !tag_exists_locally {
+        run_git(&[
+            "-c",
+            &format!("user.name={}", config.signature.name),
+            "-c",
+            &format!("user.email={}", config.signature.email),
+            "tag",
+            "-a",
+            &tag,
+            "-m",
+            changelog_body,
+        ])?;
+    }
+
+    // Pass the auth header through git's env-var config mechanism rather than a `-c`
+    // argv flag: `-c http.extraHeader=...Bearer <token>` would put the secret in the
+    // process's argv, visible to any other process on the box for the life of the push
+    // via `ps aux` / `/proc/<pid>/cmdline`. GIT_CONFIG_COUNT/KEY/VALUE env vars aren't
+    // exposed that way.
+    let push_result = run_git_with_env(
+        &["push", &config.remote, &tag],
+        &[
+            ("GIT_CONFIG_COUNT", "1".to_string()),
+            ("GIT_CONFIG_KEY_0", "http.extraHeader".to_string()),
+            (
+                "GIT_CONFIG_VALUE_0",
+                format!("AUTHORIZATION: Bearer {token}"),
+            ),
+        ],
+    );
+
+    if let Err(e) = push_result {
+        if !tag_exists_locally {
+            // Roll back the tag we just created so a retry doesn't find a stale local
+            // ref, skip creating a fresh tag, and push the old one instead.
+            let _ = run_git(&["tag", "-d", &tag]);
+        }
+        return Err(e);
+    }
+
+    Ok(true)
+}